@@ -1,6 +1,7 @@
 use std::{
     cmp::{Ordering, PartialOrd},
     fmt::Display,
+    rc::Rc,
 };
 
 use vec1::{vec1, Vec1};
@@ -31,7 +32,7 @@ impl Prefixable for &str {
     }
 }
 
-impl<'a, T: Prefixable> Prefixable for &'a T {
+impl<T: Prefixable> Prefixable for &T {
     fn is_prefix_of(&self, other: &Self) -> bool {
         (*other).is_prefix_of(*self)
     }
@@ -47,13 +48,40 @@ macro_rules! tree {
     }};
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoseTree<T> {
     pub value: T,
     pub children: Vec<RoseTree<T>>,
 }
 
+/// Glyphs and spacing used by [`RoseTree::fmt_tree`] to draw a connected-line,
+/// `tree(1)`-style rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Indenter {
+    pub vertical_bar: &'static str,
+    pub inward_branch: &'static str,
+    pub horizontal_bar: &'static str,
+    pub last_entry: &'static str,
+    pub level_width: usize,
+    pub ignore_root: bool,
+}
+
+impl Default for Indenter {
+    fn default() -> Self {
+        Indenter {
+            vertical_bar: "\u{2502}",
+            inward_branch: "\u{251c}",
+            horizontal_bar: "\u{2500}",
+            last_entry: "\u{2514}",
+            level_width: 4,
+            ignore_root: true,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndentationError {
     EmptyIterator,
     IncoherentIndent,
@@ -61,8 +89,21 @@ pub enum IndentationError {
     Internal,
 }
 
+impl Display for IndentationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndentationError::EmptyIterator => write!(f, "no lines were given to indent"),
+            IndentationError::IncoherentIndent => {
+                write!(f, "indentation is neither a prefix nor an extension of the current level's indentation, or mixes tabs and spaces against the detected indent style")
+            }
+            IndentationError::InvalidIndent => write!(f, "indentation does not match any enclosing level"),
+            IndentationError::Internal => write!(f, "internal indentation bookkeeping error"),
+        }
+    }
+}
+
 impl<T> RoseTree<T> {
-    pub fn to_doc(&self, vertical: bool) -> Doc<BoxDoc<()>>
+    pub fn to_doc(&self, vertical: bool) -> Doc<'_, BoxDoc<'_, ()>>
     where
         T: Display,
     {
@@ -73,7 +114,7 @@ impl<T> RoseTree<T> {
             if vertical {
                 let head = Doc::as_string(&self.value).append(Doc::newline());
                 let child_docs = Doc::intersperse(
-                    children.into_iter().map(|c| c.to_doc(vertical)),
+                    children.iter().map(|c| c.to_doc(vertical)),
                     Doc::newline(),
                 )
                 .append(Doc::newline());
@@ -84,7 +125,7 @@ impl<T> RoseTree<T> {
                     .append(Doc::text("=>"))
                     .append(Doc::newline());
                 let child_docs = Doc::space().append(Doc::intersperse(
-                    children.into_iter().map(|c| c.to_doc(vertical)),
+                    children.iter().map(|c| c.to_doc(vertical)),
                     ", ",
                 ));
                 head.append(child_docs.nest(2).group())
@@ -92,6 +133,101 @@ impl<T> RoseTree<T> {
         }
     }
 
+    /// Renders the tree using connected box-drawing lines, e.g.:
+    ///
+    /// ```text
+    /// 0
+    /// ├── 1
+    /// ├── 2
+    /// └── 3
+    ///     └── 4
+    /// ```
+    pub fn fmt_tree(&self, indenter: &Indenter) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::new();
+        self.fmt_tree_lines(indenter, &mut vec![], true, indenter.ignore_root, &mut out);
+        out
+    }
+
+    /// Serializes the tree back to indented lines, one per node, in
+    /// pre-order: the node's value prefixed by `style.as_str()` repeated
+    /// once per level of depth. Paired with [`RoseTree::from_indented_str`],
+    /// `parse -> serialize -> parse` is a fixed point for well-formed input.
+    pub fn to_indented_lines(&self, style: IndentStyle) -> Vec<String>
+    where
+        T: Display,
+    {
+        let mut lines = vec![];
+        self.write_indented_lines(style, 0, &mut lines);
+        lines
+    }
+
+    fn write_indented_lines(&self, style: IndentStyle, depth: usize, lines: &mut Vec<String>)
+    where
+        T: Display,
+    {
+        lines.push(format!("{}{}", style.as_str().repeat(depth), self.value));
+        for child in &self.children {
+            child.write_indented_lines(style, depth + 1, lines);
+        }
+    }
+
+    /// Serializes an entire forest back to indented lines, concatenating
+    /// each tree's lines in order.
+    pub fn forest_to_indented_lines(forest: &[Self], style: IndentStyle) -> Vec<String>
+    where
+        T: Display,
+    {
+        forest
+            .iter()
+            .flat_map(|t| t.to_indented_lines(style))
+            .collect()
+    }
+
+    fn fmt_tree_lines(
+        &self,
+        indenter: &Indenter,
+        ancestors_have_more: &mut Vec<bool>,
+        is_last: bool,
+        suppress_self: bool,
+        out: &mut String,
+    ) where
+        T: Display,
+    {
+        if !suppress_self {
+            for &has_more in ancestors_have_more.iter() {
+                if has_more {
+                    out.push_str(indenter.vertical_bar);
+                    out.push_str(&" ".repeat(indenter.level_width.saturating_sub(1)));
+                } else {
+                    out.push_str(&" ".repeat(indenter.level_width));
+                }
+            }
+            out.push_str(if is_last {
+                indenter.last_entry
+            } else {
+                indenter.inward_branch
+            });
+            out.push_str(&indenter.horizontal_bar.repeat(indenter.level_width.saturating_sub(2)));
+            out.push(' ');
+        }
+        out.push_str(&self.value.to_string());
+        out.push('\n');
+
+        let last_idx = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            if !suppress_self {
+                ancestors_have_more.push(!is_last);
+            }
+            child.fmt_tree_lines(indenter, ancestors_have_more, i == last_idx, false, out);
+            if !suppress_self {
+                ancestors_have_more.pop();
+            }
+        }
+    }
+
     pub fn node(value: T) -> Self {
         RoseTree {
             value,
@@ -103,6 +239,50 @@ impl<T> RoseTree<T> {
         RoseTree { value, children }
     }
 
+    /// Transforms every value in the tree, pre-order, keeping the shape intact.
+    pub fn map<U>(self, f: impl FnMut(&T) -> U) -> RoseTree<U> {
+        fn go<T, U, F: FnMut(&T) -> U>(t: RoseTree<T>, f: &mut F) -> RoseTree<U> {
+            let value = f(&t.value);
+            let children = t.children.into_iter().map(|c| go(c, f)).collect();
+            RoseTree { value, children }
+        }
+
+        let mut f = f;
+        go(self, &mut f)
+    }
+
+    /// Threads an accumulator through the whole tree, pre-order: the node's
+    /// own value folds in before its children's.
+    pub fn fold<B>(&self, init: B, f: impl FnMut(B, &T) -> B) -> B {
+        fn go<T, B, F: FnMut(B, &T) -> B>(t: &RoseTree<T>, acc: B, f: &mut F) -> B {
+            let acc = f(acc, &t.value);
+            t.children.iter().fold(acc, |acc, c| go(c, acc, f))
+        }
+
+        let mut f = f;
+        go(self, init, &mut f)
+    }
+
+    /// Drops nodes whose closure returns `None`, re-parenting their
+    /// surviving descendants onto the nearest retained ancestor — the same
+    /// reattachment `from_prefixables` does in [`RoseTree::prune_down`] when
+    /// popping a level off the indentation stack. Since the root itself may
+    /// be dropped, the result is a forest rather than a single tree.
+    pub fn filter_map<U>(self, f: impl FnMut(&T) -> Option<U>) -> Vec<RoseTree<U>> {
+        fn go<T, U, F: FnMut(&T) -> Option<U>>(t: RoseTree<T>, f: &mut F) -> Vec<RoseTree<U>> {
+            let children: Vec<RoseTree<U>> =
+                t.children.into_iter().flat_map(|c| go(c, f)).collect();
+
+            match f(&t.value) {
+                Some(value) => vec![RoseTree { value, children }],
+                None => children,
+            }
+        }
+
+        let mut f = f;
+        go(self, &mut f)
+    }
+
     pub fn from_prefixables<Pr: Prefixable>(
         mut iter: impl Iterator<Item = (Pr, T)>,
     ) -> Result<Vec<Self>, IndentationError> {
@@ -153,7 +333,7 @@ impl<T> RoseTree<T> {
     }
 
     fn prune_down<Pr>(stack: &mut Vec1<(Pr, Vec1<Self>)>) {
-        while let Ok((_, v)) = stack.try_pop() {
+        while let Ok((_, v)) = stack.pop() {
             let mut highest = v.into_vec();
             stack.last_mut().1.last_mut().children.append(&mut highest);
         }
@@ -162,24 +342,501 @@ impl<T> RoseTree<T> {
     fn valid_indent<Pr: Prefixable>(target_indent: &Pr, stack: &Vec1<(Pr, Vec1<Self>)>) -> bool {
         stack
             .into_iter()
-            .any(|&(ref p, _)| p.prefix_ord(target_indent) == Some(Ordering::Equal))
+            .any(|(p, _)| p.prefix_ord(target_indent) == Some(Ordering::Equal))
     }
 
     fn prune_down_to<Pr: Prefixable>(target_indent: &Pr, stack: &mut Vec1<(Pr, Vec1<Self>)>) {
         while target_indent.prefix_ord(&stack.last().0) == Some(Ordering::Less) {
-            let mut highest = stack.try_pop().unwrap().1.into_vec();
+            let mut highest = stack.pop().unwrap().1.into_vec();
             stack.last_mut().1.last_mut().children.append(&mut highest);
         }
     }
 }
 
+/// The leading-whitespace convention detected in a block of indented text.
+///
+/// `Spaces(n)` records the width of a single indent level, which must fall
+/// in the range `1..=16`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(u8),
+}
+
+impl IndentStyle {
+    /// The text that represents a single indent level under this style.
+    pub fn as_str(&self) -> String {
+        match self {
+            IndentStyle::Tabs => "\t".to_string(),
+            IndentStyle::Spaces(width) => " ".repeat(*width as usize),
+        }
+    }
+}
+
+impl RoseTree<String> {
+    /// Parses raw indented text into a forest, splitting each non-blank line
+    /// into its leading whitespace and trailing content and feeding the
+    /// resulting pairs into [`RoseTree::from_prefixables`].
+    ///
+    /// The indent style (tabs vs. spaces, and the space width) is inferred
+    /// from the first indented line; lines whose leading whitespace mixes
+    /// tabs and spaces against that detected style are rejected with
+    /// [`IndentationError::IncoherentIndent`] — unless they repeat the
+    /// exact prefix that defined the style, which is coherent with it by
+    /// construction.
+    pub fn from_indented_str(input: &str) -> Result<Vec<Self>, IndentationError> {
+        let mut style: Option<IndentStyle> = None;
+        let mut base_prefix: Option<&str> = None;
+        let mut prefixables = vec![];
+
+        for line in input.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+            let (prefix, content) = line.split_at(indent_len);
+
+            if !prefix.is_empty() {
+                let detected = match style {
+                    Some(detected) => detected,
+                    None => {
+                        let detected = Self::detect_indent_style(prefix)?;
+                        style = Some(detected);
+                        base_prefix = Some(prefix);
+                        detected
+                    }
+                };
+
+                // A prefix identical to the one that defined the baseline is
+                // coherent with it by construction, even if that baseline
+                // itself mixes tabs and spaces; only prefixes that diverge
+                // from it need checking against the detected style.
+                if Some(prefix) != base_prefix {
+                    match detected {
+                        IndentStyle::Tabs => {
+                            if prefix.bytes().any(|b| b != b'\t') {
+                                return Err(IndentationError::IncoherentIndent);
+                            }
+                        }
+                        IndentStyle::Spaces(_) => {
+                            if prefix.bytes().any(|b| b == b'\t') {
+                                return Err(IndentationError::IncoherentIndent);
+                            }
+                        }
+                    }
+                }
+            }
+
+            prefixables.push((prefix, content.to_owned()));
+        }
+
+        Self::from_prefixables(prefixables.into_iter())
+    }
+
+    fn detect_indent_style(prefix: &str) -> Result<IndentStyle, IndentationError> {
+        if prefix.starts_with('\t') {
+            Ok(IndentStyle::Tabs)
+        } else {
+            let width = prefix.len();
+            if width == 0 || width > 16 {
+                return Err(IndentationError::InvalidIndent);
+            }
+            Ok(IndentStyle::Spaces(width as u8))
+        }
+    }
+}
+
+/// A navigable, immutable cursor over a [`RoseTree`], in the rust-analyzer
+/// zipper style. Navigation (`parent`, `first_child`, `next_sibling`,
+/// `prev_sibling`) moves a path of child indices around a shared root, so
+/// each step is `O(depth)`. Edits (`insert_child`, `replace_value`,
+/// `remove`) return a cursor over a freshly-built tree rather than
+/// mutating in place: only the nodes along the cursor's path are rebuilt,
+/// each holding `Rc`-shared pointers to its untouched siblings, so an edit
+/// costs `O(depth)` rather than `O(size of the edited subtree)`. The tree
+/// the cursor was built from is left untouched. Call
+/// [`RoseTreeCursor::to_tree`] to materialize the cursor's root into a
+/// plain [`RoseTree`].
+#[derive(Debug, Clone)]
+pub struct RoseTreeCursor<T> {
+    root: Rc<CursorNode<T>>,
+    path: Vec<usize>,
+}
+
+/// The cursor's own node representation: like [`RoseTree`], but with
+/// children behind `Rc` so that rebuilding the path to an edited node can
+/// share its untouched siblings instead of deep-cloning them.
+#[derive(Debug)]
+struct CursorNode<T> {
+    value: T,
+    children: Vec<Rc<CursorNode<T>>>,
+}
+
+impl<T: Clone> CursorNode<T> {
+    fn from_tree(tree: &RoseTree<T>) -> Self {
+        CursorNode {
+            value: tree.value.clone(),
+            children: tree
+                .children
+                .iter()
+                .map(|c| Rc::new(Self::from_tree(c)))
+                .collect(),
+        }
+    }
+
+    fn to_tree(&self) -> RoseTree<T> {
+        RoseTree {
+            value: self.value.clone(),
+            children: self.children.iter().map(|c| c.to_tree()).collect(),
+        }
+    }
+}
+
+impl<T: Clone> RoseTreeCursor<T> {
+    /// Creates a cursor positioned on the root of `root`.
+    pub fn new(root: RoseTree<T>) -> Self {
+        RoseTreeCursor {
+            root: Rc::new(CursorNode::from_tree(&root)),
+            path: vec![],
+        }
+    }
+
+    /// The value of the node the cursor is currently positioned on.
+    pub fn value(&self) -> &T {
+        &self.current_node().value
+    }
+
+    /// Materializes the cursor's root tree into an owned [`RoseTree`],
+    /// including any edits made along the way.
+    pub fn to_tree(&self) -> RoseTree<T> {
+        self.root.to_tree()
+    }
+
+    fn current_node(&self) -> &CursorNode<T> {
+        let mut node = self.root.as_ref();
+        for &i in &self.path {
+            node = &node.children[i];
+        }
+        node
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let mut path = self.path.clone();
+        path.pop()?;
+        Some(RoseTreeCursor {
+            root: Rc::clone(&self.root),
+            path,
+        })
+    }
+
+    pub fn first_child(&self) -> Option<Self> {
+        if self.current_node().children.is_empty() {
+            return None;
+        }
+        let mut path = self.path.clone();
+        path.push(0);
+        Some(RoseTreeCursor {
+            root: Rc::clone(&self.root),
+            path,
+        })
+    }
+
+    pub fn next_sibling(&self) -> Option<Self> {
+        let last = *self.path.last()?;
+        if last + 1 >= self.siblings_len() {
+            return None;
+        }
+        let mut path = self.path.clone();
+        *path.last_mut().unwrap() = last + 1;
+        Some(RoseTreeCursor {
+            root: Rc::clone(&self.root),
+            path,
+        })
+    }
+
+    pub fn prev_sibling(&self) -> Option<Self> {
+        let last = *self.path.last()?;
+        let last = last.checked_sub(1)?;
+        let mut path = self.path.clone();
+        *path.last_mut().unwrap() = last;
+        Some(RoseTreeCursor {
+            root: Rc::clone(&self.root),
+            path,
+        })
+    }
+
+    fn siblings_len(&self) -> usize {
+        let mut node = self.root.as_ref();
+        for &i in &self.path[..self.path.len() - 1] {
+            node = &node.children[i];
+        }
+        node.children.len()
+    }
+
+    /// Returns a cursor, positioned on the same node, over a tree with the
+    /// current node's value replaced.
+    pub fn replace_value(&self, value: T) -> Self {
+        RoseTreeCursor {
+            root: self.rebuild(&self.path, |mut node| {
+                node.value = value;
+                node
+            }),
+            path: self.path.clone(),
+        }
+    }
+
+    /// Returns a cursor, positioned on the same node, over a tree with
+    /// `child` appended to the current node's children.
+    pub fn insert_child(&self, child: RoseTree<T>) -> Self {
+        RoseTreeCursor {
+            root: self.rebuild(&self.path, |mut node| {
+                node.children.push(Rc::new(CursorNode::from_tree(&child)));
+                node
+            }),
+            path: self.path.clone(),
+        }
+    }
+
+    /// Returns a cursor, positioned on the parent, over a tree with the
+    /// current node removed from it. Returns `None` when the cursor is on
+    /// the root, since there is no parent to remove it from.
+    pub fn remove(&self) -> Option<Self> {
+        let (&index, parent_path) = self.path.split_last()?;
+        let parent_path = parent_path.to_vec();
+        let root = self.rebuild(&parent_path, |mut node| {
+            node.children.remove(index);
+            node
+        });
+        Some(RoseTreeCursor {
+            root,
+            path: parent_path,
+        })
+    }
+
+    /// Rebuilds the nodes from the root down to `path`, applying `edit` to
+    /// the node found there. Every sibling off `path` is `Rc`-shared from
+    /// the cursor's current root rather than cloned, so the cost is
+    /// `O(depth)`, not `O(size of the affected subtree)`.
+    fn rebuild(
+        &self,
+        path: &[usize],
+        edit: impl FnOnce(CursorNode<T>) -> CursorNode<T>,
+    ) -> Rc<CursorNode<T>> {
+        fn go<T: Clone>(
+            node: &Rc<CursorNode<T>>,
+            path: &[usize],
+            edit: impl FnOnce(CursorNode<T>) -> CursorNode<T>,
+        ) -> Rc<CursorNode<T>> {
+            match path.split_first() {
+                None => Rc::new(edit(CursorNode {
+                    value: node.value.clone(),
+                    children: node.children.clone(),
+                })),
+                Some((&i, rest)) => {
+                    let mut children = node.children.clone();
+                    children[i] = go(&node.children[i], rest, edit);
+                    Rc::new(CursorNode {
+                        value: node.value.clone(),
+                        children,
+                    })
+                }
+            }
+        }
+
+        go(&self.root, path, edit)
+    }
+}
+
+/// Wraps a [`RoseTree<String>`] so it can be deserialized from either the
+/// ordinary nested `{value, children}` form derived for [`RoseTree`], or a
+/// flat list of indented strings run through [`RoseTree::from_indented_str`].
+/// Accepting the flat form only makes sense for string-valued trees, and
+/// Rust's coherence rules don't allow a second, narrower `Deserialize` impl
+/// for `RoseTree<String>` alongside the derived blanket one — so the flat
+/// form lives on this separate wrapper type instead.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndentedOrNested(pub RoseTree<String>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IndentedOrNested {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Nested(RoseTree<String>),
+            Flat(Vec<String>),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Nested(tree) => Ok(IndentedOrNested(tree)),
+            Repr::Flat(lines) => {
+                let mut forest = RoseTree::<String>::from_indented_str(&lines.join("\n"))
+                    .map_err(serde::de::Error::custom)?;
+                match (forest.pop(), forest.is_empty()) {
+                    (Some(root), true) => Ok(IndentedOrNested(root)),
+                    _ => Err(serde::de::Error::custom(
+                        "flat indented form must describe exactly one root",
+                    )),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IndentedOrNested {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn fmt_tree_draws_box_lines() {
+        let t = tree![0 =>
+            tree![1],
+            tree![2],
+            tree![3 => tree![4]]
+        ];
+
+        assert_eq!(
+            t.fmt_tree(&Indenter::default()),
+            "0\n├── 1\n├── 2\n└── 3\n    └── 4\n"
+        );
+    }
+
+    #[test]
+    fn from_indented_str_parses_spaces() {
+        let input = "root\n  child one\n  child two\n    grandchild\n";
+
+        assert_eq!(
+            RoseTree::from_indented_str(input),
+            Ok(vec![tree!["root".to_string() =>
+                tree!["child one".to_string()],
+                tree!["child two".to_string() => tree!["grandchild".to_string()]],
+            ]])
+        );
+    }
+
+    #[test]
+    fn from_indented_str_rejects_mixed_indent() {
+        let input = "root\n  child\n\tother\n";
+
+        assert_eq!(
+            RoseTree::from_indented_str(input),
+            Err(IndentationError::IncoherentIndent)
+        );
+    }
+
+    #[test]
+    fn from_indented_str_accepts_repeated_mixed_prefix_as_its_own_baseline() {
+        let input = "root\n \tchild one\n \tchild two\n";
+
+        assert_eq!(
+            RoseTree::from_indented_str(input),
+            Ok(vec![tree!["root".to_string() =>
+                tree!["child one".to_string()],
+                tree!["child two".to_string()],
+            ]])
+        );
+    }
+
+    #[test]
+    fn cursor_navigates_and_replaces_without_mutating_original() {
+        let original = tree![1 => tree![2], tree![3]];
+        let cursor = RoseTreeCursor::new(original.clone());
+
+        let second_child = cursor.first_child().unwrap().next_sibling().unwrap();
+        assert_eq!(*second_child.value(), 3);
+
+        let edited = second_child.replace_value(30);
+        assert_eq!(edited.to_tree(), tree![1 => tree![2], tree![30]]);
+        assert_eq!(cursor.to_tree(), original, "the cursor's own tree is untouched");
+    }
+
+    #[test]
+    fn cursor_inserts_and_removes_children() {
+        let cursor = RoseTreeCursor::new(tree![1 => tree![2]]);
+
+        let with_new_child = cursor.insert_child(tree![3]);
+        assert_eq!(with_new_child.to_tree(), tree![1 => tree![2], tree![3]]);
+
+        let first = cursor.first_child().unwrap();
+        let without_first_child = first.remove().unwrap();
+        assert_eq!(without_first_child.to_tree(), tree![1]);
+
+        assert!(cursor.remove().is_none(), "the root has no parent to remove it from");
+    }
+
+    #[test]
+    fn map_transforms_every_value() {
+        let t = tree![1 => tree![2], tree![3 => tree![4]]];
+
+        assert_eq!(
+            t.map(|v| v * 10),
+            tree![10 => tree![20], tree![30 => tree![40]]]
+        );
+    }
+
+    #[test]
+    fn fold_sums_every_value() {
+        let t = tree![1 => tree![2], tree![3 => tree![4]]];
+
+        assert_eq!(t.fold(0, |acc, v| acc + v), 10);
+    }
+
+    #[test]
+    fn filter_map_reparents_orphaned_children() {
+        let t = tree![1 => tree![2 => tree![3]], tree![4]];
+
+        assert_eq!(
+            t.filter_map(|v| if *v == 2 { None } else { Some(*v) }),
+            vec![tree![1 => tree![3], tree![4]]]
+        );
+    }
+
+    #[test]
+    fn filter_map_can_drop_the_root() {
+        let t = tree![1 => tree![2], tree![3]];
+
+        assert_eq!(
+            t.filter_map(|v| if *v == 1 { None } else { Some(*v) }),
+            vec![tree![2], tree![3]]
+        );
+    }
+
+    #[test]
+    fn to_indented_lines_round_trips_through_from_indented_str() {
+        let input = "root\n  child one\n  child two\n    grandchild\n";
+
+        let forest = RoseTree::from_indented_str(input).unwrap();
+        let lines = RoseTree::forest_to_indented_lines(&forest, IndentStyle::Spaces(2));
+        let reparsed = RoseTree::from_indented_str(&lines.join("\n")).unwrap();
+
+        assert_eq!(forest, reparsed);
+    }
+
+    #[test]
+    fn fmt_tree_can_stop_ignoring_root() {
+        let t = tree![0];
+
+        assert_eq!(
+            t.fmt_tree(&Indenter {
+                ignore_root: false,
+                ..Indenter::default()
+            }),
+            "└── 0\n"
+        );
+    }
+
     #[test]
     fn increasing_lines_trees() {
-        let increasing_lines: Vec<(&str, i32)> = vec![(&"", 1), (&" ", 2), (&"  ", 3), (&"   ", 4)];
+        let increasing_lines: Vec<(&str, i32)> = vec![("", 1), (" ", 2), ("  ", 3), ("   ", 4)];
 
         assert_eq!(
             RoseTree::from_prefixables(increasing_lines.into_iter()),
@@ -189,7 +846,7 @@ mod tests {
 
     #[test]
     fn constant_indentation_trees() {
-        let constant_lines: Vec<(&str, i32)> = vec![(&"", 1), (&"", 2), (&"", 3), (&"", 4)];
+        let constant_lines: Vec<(&str, i32)> = vec![("", 1), ("", 2), ("", 3), ("", 4)];
 
         assert_eq!(
             RoseTree::from_prefixables(constant_lines.into_iter()),
@@ -199,7 +856,7 @@ mod tests {
 
     #[test]
     fn incoherent_indentation() {
-        let incoherent_lines: Vec<(&str, i32)> = vec![(&"", 1), (&" ", 2), (&"\t", 3)];
+        let incoherent_lines: Vec<(&str, i32)> = vec![("", 1), (" ", 2), ("\t", 3)];
 
         assert_eq!(
             RoseTree::from_prefixables(incoherent_lines.into_iter()),
@@ -210,12 +867,12 @@ mod tests {
     #[test]
     fn base_indent_respected() {
         let off_base_lines: Vec<(&str, i32)> = vec![
-            (&" ", 1),
-            (&"  ", 2),
-            (&" ", 3),
-            (&"  ", 4),
-            (&"  ", 5),
-            (&" ", 6),
+            (" ", 1),
+            ("  ", 2),
+            (" ", 3),
+            ("  ", 4),
+            ("  ", 5),
+            (" ", 6),
         ];
 
         assert_eq!(
@@ -230,11 +887,57 @@ mod tests {
 
     #[test]
     fn base_indent_is_invalid_indent_error() {
-        let wrong_base_lines: Vec<(&str, i32)> = vec![(&" ", 1), (&"", 2)];
+        let wrong_base_lines: Vec<(&str, i32)> = vec![(" ", 1), ("", 2)];
 
         assert_eq!(
             RoseTree::from_prefixables(wrong_base_lines.into_iter()),
             Err(IndentationError::InvalidIndent)
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn indented_or_nested_round_trips_through_the_nested_form() {
+        let original = IndentedOrNested(tree!["root".to_string() =>
+            tree!["child one".to_string()],
+            tree!["child two".to_string() => tree!["grandchild".to_string()]],
+        ]);
+
+        let json = serde_json::to_string(&original).unwrap();
+        let reparsed: IndentedOrNested = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(reparsed, original);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn indented_or_nested_parses_the_flat_form() {
+        let json = serde_json::json!(["root", "  child one", "  child two"]);
+
+        let parsed: IndentedOrNested = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            parsed,
+            IndentedOrNested(tree!["root".to_string() =>
+                tree!["child one".to_string()],
+                tree!["child two".to_string()],
+            ])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn indented_or_nested_rejects_a_flat_form_without_exactly_one_root() {
+        let multi_root = serde_json::json!(["root one", "root two"]);
+        let multi_root_err = serde_json::from_value::<IndentedOrNested>(multi_root).unwrap_err();
+        assert!(multi_root_err
+            .to_string()
+            .contains("flat indented form must describe exactly one root"));
+
+        let zero_roots = serde_json::json!([] as [String; 0]);
+        let zero_roots_err = serde_json::from_value::<IndentedOrNested>(zero_roots).unwrap_err();
+        assert!(zero_roots_err
+            .to_string()
+            .contains("flat indented form must describe exactly one root"));
+    }
 }
@@ -1,5 +1,3 @@
-use vec1::{vec1, Vec1};
-
 use indenty::{tree, RoseTree};
 
 fn main() {